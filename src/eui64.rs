@@ -0,0 +1,259 @@
+//! EUI-64 addresses, as used by IPv6 interface identifiers and other
+//! extended-identifier network protocols.
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(all(feature = "std", any(feature = "serde", test)))]
+use std::format;
+
+#[cfg(all(not(feature = "std"), feature = "alloc", any(feature = "serde", test)))]
+use crate::alloc::format;
+
+use crate::{MacAddress, MacAddressErrors};
+
+pub type Eui64ByteTuple = (u8, u8, u8, u8, u8, u8, u8, u8);
+
+/// A 64-bit Extended Unique Identifier, as used for IPv6 interface
+/// identifiers and other EUI-64 network protocols.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Eui64 {
+    bytes: [u8; 8],
+}
+
+impl Eui64 {
+    /// Construct a new, empty, Eui64
+    pub fn new() -> Eui64 {
+        Eui64 {
+            bytes: [0, 0, 0, 0, 0, 0, 0, 0],
+        }
+    }
+
+    /// Create an Eui64 from a set of individual bytes
+    // 8 arguments is inherent to mirroring `MacAddress::from_bytes`'s
+    // constructor surface for an 8-byte address.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_bytes(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8) -> Eui64 {
+        Eui64 {
+            bytes: [a, b, c, d, e, f, g, h],
+        }
+    }
+
+    /// Create an Eui64 from a tuple of bytes
+    pub fn from_byte_tuple(bytes: Eui64ByteTuple) -> Eui64 {
+        Eui64 {
+            bytes: [
+                bytes.0, bytes.1, bytes.2, bytes.3, bytes.4, bytes.5, bytes.6, bytes.7,
+            ],
+        }
+    }
+
+    /// Create an Eui64 from a byte array
+    pub fn from_byte_array(bytes: [u8; 8]) -> Eui64 {
+        Eui64 { bytes }
+    }
+
+    pub fn from_byte_slice(bytes: &[u8]) -> Result<Eui64, MacAddressErrors> {
+        let len = bytes.len();
+        if len == 8 {
+            let mut a: [u8; 8] = Default::default();
+            a.copy_from_slice(&bytes[0..8]);
+            Ok(a.into())
+        } else {
+            Err(MacAddressErrors::InvalidLength(len))
+        }
+    }
+
+    /// Attempt to recover the original EUI-48 (MAC) address this Eui64 was
+    /// expanded from.
+    ///
+    /// This only succeeds if bytes 4-5 are the standard `FF:FE` insertion
+    /// performed by [`MacAddress::to_eui64`]; otherwise this Eui64 was never
+    /// derived from a MAC address and `NotEui48Derived` is returned.
+    pub fn try_into_eui48(&self) -> Result<MacAddress, MacAddressErrors> {
+        if self.bytes[3] == 0xFF && self.bytes[4] == 0xFE {
+            Ok(MacAddress::from_bytes(
+                self.bytes[0],
+                self.bytes[1],
+                self.bytes[2],
+                self.bytes[5],
+                self.bytes[6],
+                self.bytes[7],
+            ))
+        } else {
+            Err(MacAddressErrors::NotEui48Derived)
+        }
+    }
+}
+
+impl Default for Eui64 {
+    fn default() -> Eui64 {
+        Eui64::new()
+    }
+}
+
+impl fmt::Display for Eui64 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            self.bytes[0],
+            self.bytes[1],
+            self.bytes[2],
+            self.bytes[3],
+            self.bytes[4],
+            self.bytes[5],
+            self.bytes[6],
+            self.bytes[7]
+        )
+    }
+}
+
+impl fmt::Debug for Eui64 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Eui64 {
+    /// Serializes the EUI-64 address.
+    ///
+    /// It serializes either to a string or its binary representation, depending on what the format
+    /// prefers.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("{}", self))
+        } else {
+            serializer.serialize_bytes(&self.bytes)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Eui64 {
+    /// Deserializes the EUI-64 address.
+    ///
+    /// It deserializes it from either a byte array (of size 8) or a string. If the format is
+    /// self-descriptive (like JSON or MessagePack), it auto-detects it. If not, it obeys the
+    /// human-readable property of the deserializer.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Eui64Visitor;
+
+        impl<'de> de::Visitor<'de> for Eui64Visitor {
+            type Value = Eui64;
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Eui64, E> {
+                value.parse().map_err(|err| E::custom(format!("{}", err)))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Eui64, E> {
+                Eui64::from_byte_slice(v).map_err(|_| E::invalid_length(v.len(), &self))
+            }
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    formatter,
+                    "either a string representation of an EUI-64 address or 8-element byte array"
+                )
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Eui64Visitor)
+        } else {
+            deserializer.deserialize_bytes(Eui64Visitor)
+        }
+    }
+}
+
+impl From<Eui64ByteTuple> for Eui64 {
+    fn from(target: Eui64ByteTuple) -> Self {
+        Eui64::from_byte_tuple(target)
+    }
+}
+
+impl From<[u8; 8]> for Eui64 {
+    fn from(target: [u8; 8]) -> Self {
+        Eui64::from_byte_array(target)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for Eui64 {
+    type Error = MacAddressErrors;
+
+    fn try_from(target: &'a [u8]) -> Result<Eui64, MacAddressErrors> {
+        Eui64::from_byte_slice(target)
+    }
+}
+
+impl FromStr for Eui64 {
+    type Err = MacAddressErrors;
+
+    fn from_str(s: &str) -> Result<Eui64, MacAddressErrors> {
+        crate::parse_hex_groups::<8>(s, ':').map(Eui64::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eui64_from_str() {
+        assert_eq!(
+            "00:00:00:00:00:00:00:00".parse(),
+            Ok(Eui64 {
+                bytes: [0, 0, 0, 0, 0, 0, 0, 0]
+            })
+        );
+        assert_eq!(
+            "11:22:33:ff:fe:44:55:66".parse(),
+            Ok(Eui64 {
+                bytes: [0x11, 0x22, 0x33, 0xFF, 0xFE, 0x44, 0x55, 0x66]
+            })
+        );
+        assert_eq!(
+            "11:22:33:44:55".parse::<Eui64>(),
+            Err(MacAddressErrors::InvalidLength(5))
+        );
+        assert_eq!(
+            "xx:xx:xx:xx:xx:xx:xx:xx".parse::<Eui64>(),
+            Err(MacAddressErrors::InvalidComponent)
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn eui64_from_bytes() {
+        assert_eq!(
+            format!(
+                "{}",
+                Eui64::from_bytes(0x11, 0x22, 0x33, 0xFF, 0xFE, 0x44, 0x55, 0x66)
+            ),
+            "11:22:33:ff:fe:44:55:66"
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn eui48_to_eui64_round_trip() {
+        let mac = MacAddress::from_bytes(0x11, 0x22, 0x33, 0x44, 0x55, 0x66);
+        let eui64 = mac.to_eui64();
+        assert_eq!(format!("{}", eui64), "11:22:33:ff:fe:44:55:66");
+        assert_eq!(eui64.try_into_eui48(), Ok(mac));
+    }
+
+    #[test]
+    fn eui64_not_derived_from_eui48() {
+        let eui64 = Eui64::from_bytes(0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88);
+        assert_eq!(
+            eui64.try_into_eui48(),
+            Err(MacAddressErrors::NotEui48Derived)
+        );
+    }
+}
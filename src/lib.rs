@@ -1,17 +1,43 @@
-#![feature(try_from)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! 'mac' provides a common structure for, surprisingly,
 //! Mac Addresses across cooperating network libraries.
-
+//!
+//! The crate builds without `std` when the default `std` feature is
+//! disabled; the `alloc` feature additionally brings in the string
+//! formatting helpers (`to_string_with`, serde) for `no_std` targets that
+//! still have a global allocator. The `serde` feature depends on `alloc`
+//! in `Cargo.toml`, since (de)serializing to a human-readable string
+//! allocates.
+
+#[cfg(feature = "serde")]
 extern crate serde;
 
-use std::convert::{ TryFrom };
-use std::fmt;
-use std::str::FromStr;
+#[cfg(feature = "rand")]
+extern crate rand;
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+extern crate alloc;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
 
-// #[cfg(feature = "serde")]
+#[cfg(feature = "serde")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::{format, string::String, vec::Vec};
+
+mod eui64;
+pub use eui64::{Eui64, Eui64ByteTuple};
+
 pub type MacByteTuple = (u8, u8, u8, u8, u8, u8);
 
 /// Represents an error which occurred whilst parsing a MAC address
@@ -21,6 +47,10 @@ pub enum MacAddressErrors {
     InvalidLength (usize),
     /// One of the components contains an invalid value, eg. 00:GG:22:33:44:55
     InvalidComponent,
+    /// An EUI-64 address does not carry the `FF:FE` insertion, so it was
+    /// never derived from an EUI-48 (MAC) address and cannot be converted
+    /// back to one.
+    NotEui48Derived,
 }
 
 impl fmt::Display for MacAddressErrors {
@@ -28,7 +58,35 @@ impl fmt::Display for MacAddressErrors {
         match self {
             MacAddressErrors::InvalidLength(len) => write!(fmt, "Expected 6 components but found {:}", len),
             MacAddressErrors::InvalidComponent => write!(fmt, "Invalid component in a MAC address string"),
+            MacAddressErrors::NotEui48Derived => write!(fmt, "EUI-64 address was not derived from an EUI-48 address"),
+        }
+    }
+}
+
+/// Parse `N` hex-encoded, `sep`-separated byte groups, eg. `11:22:33` for
+/// `N = 3, sep = ':'`. Used by [`MacAddress`]'s colon- and hyphen-separated
+/// parsing and by [`Eui64`]'s `FromStr` implementation.
+pub(crate) fn parse_hex_groups<const N: usize>(
+    s: &str,
+    sep: char,
+) -> Result<[u8; N], MacAddressErrors> {
+    let mut parts = [0u8; N];
+    let mut i = 0;
+    for split in s.split(sep) {
+        if i == N {
+            return Err(MacAddressErrors::InvalidLength(i + 1));
         }
+        match u8::from_str_radix(split, 16) {
+            Ok(b) if !split.is_empty() => parts[i] = b,
+            _ => return Err(MacAddressErrors::InvalidComponent),
+        }
+        i += 1;
+    }
+
+    if i == N {
+        Ok(parts)
+    } else {
+        Err(MacAddressErrors::InvalidLength(i))
     }
 }
 
@@ -77,6 +135,48 @@ impl MacAddress {
             // Err(E::invalid_length(bytes.len(), &self))
         }
     }
+
+    /// Generate a random, locally-administered unicast MAC address, suitable
+    /// for virtual interfaces (eg. TAP/TUN devices, VPN tunnels, VM NICs)
+    /// that need a synthetic address that won't collide with real hardware
+    /// OUIs.
+    ///
+    /// This pulls entropy from `rand::thread_rng()`, which needs the OS,
+    /// so it additionally requires the `std` feature; `no_std` callers
+    /// should use [`MacAddress::random_local_with`] with their own RNG.
+    #[cfg(all(feature = "rand", feature = "std"))]
+    pub fn random_local() -> MacAddress {
+        let mut rng = rand::thread_rng();
+        MacAddress::random_local_with(&mut rng)
+    }
+
+    /// Like [`MacAddress::random_local`], but draws bytes from a
+    /// caller-supplied RNG, so callers can get a reproducible address in
+    /// tests.
+    #[cfg(feature = "rand")]
+    pub fn random_local_with<R: Rng>(rng: &mut R) -> MacAddress {
+        let mut bytes = [0u8; 6];
+        rng.fill(&mut bytes);
+        // Clear the multicast bit and set the locally-administered bit.
+        bytes[0] = (bytes[0] & 0xFC) | 0x02;
+        bytes.into()
+    }
+
+    /// Expand this EUI-48 (MAC) address into its EUI-64 form by inserting
+    /// the standard `FF:FE` bytes between the OUI and the NIC-specific
+    /// portion, e.g. `11:22:33:44:55:66` becomes `11:22:33:ff:fe:44:55:66`.
+    pub fn to_eui64(&self) -> Eui64 {
+        Eui64::from_bytes(
+            self.bytes[0],
+            self.bytes[1],
+            self.bytes[2],
+            0xFF,
+            0xFE,
+            self.bytes[3],
+            self.bytes[4],
+            self.bytes[5],
+        )
+    }
 }
 
 impl fmt::Display for MacAddress {
@@ -94,7 +194,7 @@ impl fmt::Display for MacAddress {
     }
 }
 
-// #[cfg(feature = "serde")]
+#[cfg(feature = "serde")]
 impl Serialize for MacAddress {
     /// Serializes the MAC address.
     ///
@@ -109,7 +209,7 @@ impl Serialize for MacAddress {
     }
 }
 
-// #[cfg(feature = "serde")]
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for MacAddress {
     /// Deserializes the MAC address.
     ///
@@ -123,7 +223,7 @@ impl<'de> Deserialize<'de> for MacAddress {
             type Value = MacAddress;
 
             fn visit_str<E: de::Error>(self, value: &str) -> Result<MacAddress, E> {
-                value.parse().map_err(|err| E::custom(&format!("{}", err)))
+                value.parse().map_err(|err| E::custom(format!("{}", err)))
             }
 
             fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<MacAddress, E> {
@@ -163,6 +263,12 @@ impl fmt::Debug for MacAddress {
     }
 }
 
+impl Default for MacAddress {
+    fn default() -> MacAddress {
+        MacAddress::new()
+    }
+}
+
 impl From<MacByteTuple> for MacAddress {
     fn from(target: MacByteTuple) -> Self {
         MacAddress::from_byte_tuple(target)
@@ -183,30 +289,212 @@ impl <'a> TryFrom<&'a [u8]> for MacAddress {
     }
 }
 
-impl FromStr for MacAddress {
-    type Err = MacAddressErrors;
+/// The textual notations a [`MacAddress`] can be formatted as or parsed
+/// from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MacFormat {
+    /// Hyphen-separated hex octets, eg. `11-22-33-44-55-66`
+    Canonical,
+    /// Colon-separated hex octets, eg. `11:22:33:44:55:66`
+    Colon,
+    /// Cisco-style dot-separated hex quadruplets, eg. `1122.3344.5566`
+    Dotted,
+    /// 12 hex digits with no separator, eg. `112233445566`
+    Bare,
+}
 
-    fn from_str(s: &str) -> Result<MacAddress, MacAddressErrors> {
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl MacAddress {
+    /// Render this address using the given [`MacFormat`], in either upper
+    /// or lower case hex.
+    pub fn to_string_with(&self, format: MacFormat, upper: bool) -> String {
+        let hex = |byte: u8| -> String {
+            if upper {
+                format!("{:02X}", byte)
+            } else {
+                format!("{:02x}", byte)
+            }
+        };
+
+        match format {
+            MacFormat::Canonical => self
+                .bytes
+                .iter()
+                .map(|b| hex(*b))
+                .collect::<Vec<_>>()
+                .join("-"),
+            MacFormat::Colon => self
+                .bytes
+                .iter()
+                .map(|b| hex(*b))
+                .collect::<Vec<_>>()
+                .join(":"),
+            MacFormat::Dotted => format!(
+                "{}{}.{}{}.{}{}",
+                hex(self.bytes[0]),
+                hex(self.bytes[1]),
+                hex(self.bytes[2]),
+                hex(self.bytes[3]),
+                hex(self.bytes[4]),
+                hex(self.bytes[5])
+            ),
+            MacFormat::Bare => self.bytes.iter().map(|b| hex(*b)).collect::<Vec<_>>().join(""),
+        }
+    }
+}
+
+impl MacAddress {
+    /// Parse a colon- or hyphen-separated MAC address string, eg.
+    /// `11:22:33:44:55:66` or `11-22-33-44-55-66`.
+    fn from_str_separated(s: &str, sep: char) -> Result<MacAddress, MacAddressErrors> {
+        parse_hex_groups::<6>(s, sep).map(MacAddress::from)
+    }
+
+    /// Parse a Cisco-style dotted MAC address string, eg. `1122.3344.5566`,
+    /// where each of the three groups is 4 hex digits.
+    fn from_str_dotted(s: &str) -> Result<MacAddress, MacAddressErrors> {
         let mut parts = [0u8; 6];
-        let splits = s.split(':');
         let mut i = 0;
-        for split in splits {
-            if i == 6 {
+        for group in s.split('.') {
+            if i == 3 {
                 return Err(MacAddressErrors::InvalidLength(i + 1));
             }
-            match u8::from_str_radix(split, 16) {
-                Ok(b) if split.len() != 0 => parts[i] = b,
-                _ => return Err(MacAddressErrors::InvalidComponent),
+            if group.len() != 4 {
+                return Err(MacAddressErrors::InvalidComponent);
             }
+            let value = u16::from_str_radix(group, 16)
+                .map_err(|_| MacAddressErrors::InvalidComponent)?;
+            parts[i * 2] = (value >> 8) as u8;
+            parts[i * 2 + 1] = (value & 0xFF) as u8;
             i += 1;
         }
 
-        if i == 6 {
+        if i == 3 {
             Ok(parts.into())
         } else {
             Err(MacAddressErrors::InvalidLength(i))
         }
     }
+
+    /// Parse a bare, unseparated MAC address string, eg. `112233445566`.
+    fn from_str_bare(s: &str) -> Result<MacAddress, MacAddressErrors> {
+        if s.len() != 12 {
+            return Err(MacAddressErrors::InvalidLength(s.len()));
+        }
+
+        let mut parts = [0u8; 6];
+        for (i, part) in parts.iter_mut().enumerate() {
+            let byte = &s[i * 2..i * 2 + 2];
+            *part = u8::from_str_radix(byte, 16).map_err(|_| MacAddressErrors::InvalidComponent)?;
+        }
+
+        Ok(parts.into())
+    }
+}
+
+impl FromStr for MacAddress {
+    type Err = MacAddressErrors;
+
+    /// Parses a MAC address, auto-detecting the separator (or lack
+    /// thereof) from the first non-hex-digit character: colon-separated
+    /// (`11:22:33:44:55:66`), hyphen-separated (`11-22-33-44-55-66`),
+    /// Cisco dot-triples (`1122.3344.5566`), or bare hex (`112233445566`).
+    fn from_str(s: &str) -> Result<MacAddress, MacAddressErrors> {
+        match s.chars().find(|c| !c.is_ascii_hexdigit()) {
+            Some(':') => MacAddress::from_str_separated(s, ':'),
+            Some('-') => MacAddress::from_str_separated(s, '-'),
+            Some('.') => MacAddress::from_str_dotted(s),
+            Some(_) => Err(MacAddressErrors::InvalidComponent),
+            None => MacAddress::from_str_bare(s),
+        }
+    }
+}
+
+impl MacAddress {
+    /// The Organizationally Unique Identifier: the first three bytes of
+    /// the address.
+    pub fn oui(&self) -> [u8; 3] {
+        [self.bytes[0], self.bytes[1], self.bytes[2]]
+    }
+
+    /// Whether this is a multicast address (bit 0 of the first octet is set).
+    pub fn is_multicast(&self) -> bool {
+        self.bytes[0] & 0b0000_0001 != 0
+    }
+
+    /// Whether this is a unicast address, ie. not multicast.
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Whether this is a locally-administered address (bit 1 of the first
+    /// octet is set), as opposed to one assigned from a manufacturer's OUI.
+    pub fn is_local(&self) -> bool {
+        self.bytes[0] & 0b0000_0010 != 0
+    }
+
+    /// Whether this is a universally-administered address, ie. not local.
+    pub fn is_universal(&self) -> bool {
+        !self.is_local()
+    }
+
+    /// Whether this is the broadcast address `ff:ff:ff:ff:ff:ff`.
+    pub fn is_broadcast(&self) -> bool {
+        self.bytes == [0xFF; 6]
+    }
+
+    /// Whether this is the nil address `00:00:00:00:00:00`.
+    pub fn is_nil(&self) -> bool {
+        self.bytes == [0; 6]
+    }
+
+    /// Set or clear the locally-administered bit in place.
+    pub fn set_local(&mut self, local: bool) {
+        if local {
+            self.bytes[0] |= 0b0000_0010;
+        } else {
+            self.bytes[0] &= !0b0000_0010;
+        }
+    }
+
+    /// Set or clear the multicast bit in place.
+    pub fn set_multicast(&mut self, multicast: bool) {
+        if multicast {
+            self.bytes[0] |= 0b0000_0001;
+        } else {
+            self.bytes[0] &= !0b0000_0001;
+        }
+    }
+
+    /// Read a MAC address from the front of `data`, returning the address
+    /// and the number of bytes consumed (always 6). Intended for decoding
+    /// MACs straight out of packet buffers without an intermediate
+    /// `[u8; 6]` copy.
+    pub fn read_from(data: &[u8]) -> Result<(MacAddress, usize), MacAddressErrors> {
+        if data.len() < 6 {
+            return Err(MacAddressErrors::InvalidLength(data.len()));
+        }
+
+        let mut mac = MacAddress::new();
+        mac.bytes.copy_from_slice(&data[..6]);
+        Ok((mac, 6))
+    }
+
+    /// Write this address into the front of `data`, returning the number
+    /// of bytes written (always 6).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is shorter than 6 bytes.
+    pub fn write_to(&self, data: &mut [u8]) -> usize {
+        assert!(
+            data.len() >= 6,
+            "buffer too short to hold a MAC address: {} < 6",
+            data.len()
+        );
+        data[..6].copy_from_slice(&self.bytes);
+        6
+    }
 }
 
 #[cfg(test)]
@@ -272,6 +560,147 @@ mod tests {
     }
 
     #[test]
+    fn mac_addr_from_str_hyphenated() {
+        assert_eq!(
+            "12:34:56:78:90:ab".parse::<MacAddress>(),
+            "12-34-56-78-90-ab".parse::<MacAddress>()
+        );
+        assert_eq!(
+            "12-34-56-78".parse::<MacAddress>(),
+            Err(MacAddressErrors::InvalidLength(4))
+        );
+    }
+
+    #[test]
+    fn mac_addr_from_str_dotted() {
+        assert_eq!(
+            "1234.5678.90ab".parse(),
+            Ok(MacAddress {
+                bytes: [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB]
+            })
+        );
+        assert_eq!(
+            "1234.5678".parse::<MacAddress>(),
+            Err(MacAddressErrors::InvalidLength(2))
+        );
+        assert_eq!(
+            "123.5678.90ab".parse::<MacAddress>(),
+            Err(MacAddressErrors::InvalidComponent)
+        );
+    }
+
+    #[test]
+    fn mac_addr_from_str_bare() {
+        assert_eq!(
+            "1234567890ab".parse(),
+            Ok(MacAddress {
+                bytes: [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB]
+            })
+        );
+        assert_eq!(
+            "1234567890".parse::<MacAddress>(),
+            Err(MacAddressErrors::InvalidLength(10))
+        );
+        assert_eq!(
+            "1234567890axyz".parse::<MacAddress>(),
+            Err(MacAddressErrors::InvalidComponent)
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    fn mac_addr_to_string_with() {
+        let mac = MacAddress::from_bytes(0x12, 0x34, 0x56, 0x78, 0x90, 0xAB);
+        assert_eq!(
+            mac.to_string_with(MacFormat::Canonical, false),
+            "12-34-56-78-90-ab"
+        );
+        assert_eq!(
+            mac.to_string_with(MacFormat::Colon, false),
+            "12:34:56:78:90:ab"
+        );
+        assert_eq!(
+            mac.to_string_with(MacFormat::Dotted, false),
+            "1234.5678.90ab"
+        );
+        assert_eq!(mac.to_string_with(MacFormat::Bare, false), "1234567890ab");
+        assert_eq!(mac.to_string_with(MacFormat::Bare, true), "1234567890AB");
+    }
+
+    #[test]
+    fn mac_addr_classification() {
+        let nil = MacAddress::from_bytes(0, 0, 0, 0, 0, 0);
+        assert!(nil.is_nil());
+        assert!(!nil.is_broadcast());
+        assert!(nil.is_unicast());
+        assert!(nil.is_universal());
+
+        let broadcast = MacAddress::from_bytes(0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF);
+        assert!(broadcast.is_broadcast());
+        assert!(broadcast.is_multicast());
+        assert!(broadcast.is_local());
+
+        let locally_administered = MacAddress::from_bytes(0x02, 0x11, 0x22, 0x33, 0x44, 0x55);
+        assert!(locally_administered.is_local());
+        assert!(locally_administered.is_unicast());
+
+        let multicast = MacAddress::from_bytes(0x01, 0x11, 0x22, 0x33, 0x44, 0x55);
+        assert!(multicast.is_multicast());
+        assert!(!multicast.is_unicast());
+    }
+
+    #[test]
+    fn mac_addr_oui() {
+        let mac = MacAddress::from_bytes(0x11, 0x22, 0x33, 0x44, 0x55, 0x66);
+        assert_eq!(mac.oui(), [0x11, 0x22, 0x33]);
+    }
+
+    #[test]
+    fn mac_addr_set_local_and_multicast() {
+        let mut mac = MacAddress::from_bytes(0, 0, 0, 0, 0, 0);
+        mac.set_local(true);
+        assert!(mac.is_local());
+        mac.set_local(false);
+        assert!(!mac.is_local());
+
+        mac.set_multicast(true);
+        assert!(mac.is_multicast());
+        mac.set_multicast(false);
+        assert!(!mac.is_multicast());
+    }
+
+    #[test]
+    fn mac_addr_read_from() {
+        let data = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0xAA, 0xBB];
+        let (mac, consumed) = MacAddress::read_from(&data).unwrap();
+        assert_eq!(mac, MacAddress::from_bytes(0x11, 0x22, 0x33, 0x44, 0x55, 0x66));
+        assert_eq!(consumed, 6);
+
+        assert_eq!(
+            MacAddress::read_from(&data[..4]),
+            Err(MacAddressErrors::InvalidLength(4))
+        );
+    }
+
+    #[test]
+    fn mac_addr_write_to() {
+        let mac = MacAddress::from_bytes(0x11, 0x22, 0x33, 0x44, 0x55, 0x66);
+        let mut data = [0u8; 8];
+        let written = mac.write_to(&mut data);
+        assert_eq!(written, 6);
+        assert_eq!(&data[..6], &[0x11, 0x22, 0x33, 0x44, 0x55, 0x66]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mac_addr_write_to_short_buffer_panics() {
+        let mac = MacAddress::from_bytes(0x11, 0x22, 0x33, 0x44, 0x55, 0x66);
+        let mut data = [0u8; 4];
+        mac.write_to(&mut data);
+    }
+
+    #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
     fn mac_addr_from_bytes() {
         assert_eq!(
             format!("{}", MacAddress::from_bytes(0, 0, 0, 0, 0, 0)),
@@ -294,6 +723,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
     fn mac_addr_from_byte_tuple() {
         assert_eq!(
             format!("{}", MacAddress::from_byte_tuple((0, 0, 0, 0, 0, 0))),
@@ -316,6 +746,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
     fn mac_addr_from_byte_array() {
         assert_eq!(
             format!("{}", MacAddress::from_byte_array([0, 0, 0, 0, 0, 0])),
@@ -338,6 +769,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(any(feature = "std", feature = "alloc"))]
     fn str_from_mac_addr() {
         assert_eq!(
             format!(
@@ -368,7 +800,7 @@ mod tests {
         );
     }
 
-    // #[cfg(feature = "serde")]
+    #[cfg(feature = "serde")]
     mod serde {
         extern crate serde_test;
         use self::serde_test::{
@@ -417,4 +849,28 @@ mod tests {
         }
     }
 
+    #[cfg(all(feature = "rand", feature = "std"))]
+    mod random {
+        use super::*;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        #[test]
+        fn random_local_is_unicast_and_locally_administered() {
+            let mut rng = StdRng::seed_from_u64(0);
+            let mac = MacAddress::random_local_with(&mut rng);
+            assert_eq!(mac.bytes[0] & 0b0000_0011, 0b0000_0010);
+        }
+
+        #[test]
+        fn random_local_with_is_deterministic_for_a_given_seed() {
+            let mut a = StdRng::seed_from_u64(42);
+            let mut b = StdRng::seed_from_u64(42);
+            assert_eq!(
+                MacAddress::random_local_with(&mut a),
+                MacAddress::random_local_with(&mut b)
+            );
+        }
+    }
+
 }